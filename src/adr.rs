@@ -0,0 +1,128 @@
+//! Discovering ADR files on disk and parsing them into [`Adr`]s.
+
+use crate::event::{self, Events};
+use std::fmt;
+use std::fs::{self, DirEntry};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+static ADR_EXTENSIONS: [&str; 2] = ["md", "org"];
+static ADR_NAME_PREFIX: &str = "adr-";
+
+// Copied from the docs
+fn visit_dirs(dir: &Path, cb: &mut dyn for<'r> FnMut(&'r DirEntry)) -> io::Result<()> {
+    if dir.is_dir() {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_dir() {
+                visit_dirs(&path, cb)?;
+            } else {
+                cb(&entry);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct Adr {
+    pub id: usize,
+    pub events: Events,
+}
+
+#[derive(Debug)]
+pub enum ParseEventsError {
+    Io(io::Error),
+    Parse(event::ParseError),
+}
+
+impl fmt::Display for ParseEventsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseEventsError::Io(e) => write!(f, "{}", e),
+            ParseEventsError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<io::Error> for ParseEventsError {
+    fn from(e: io::Error) -> Self {
+        ParseEventsError::Io(e)
+    }
+}
+
+impl From<event::ParseError> for ParseEventsError {
+    fn from(e: event::ParseError) -> Self {
+        ParseEventsError::Parse(e)
+    }
+}
+
+pub fn parse_events(path: &Path) -> Result<Events, ParseEventsError> {
+    Ok(event::parse(&fs::read_to_string(path)?)?)
+}
+
+/// Discovers ADR file paths and their parsed ids, sorted by id, without
+/// reading their contents. Callers that manage their own per-file parse
+/// cache (e.g. `watch`) use this instead of `collect_adrs` so they control
+/// when each file is actually re-read and re-parsed.
+pub fn discover_adr_paths() -> Vec<(usize, PathBuf)> {
+    let mut results = Vec::new();
+
+    visit_dirs(&PathBuf::from("./"), &mut |entry| {
+        let path = entry.path();
+        if let Some(ext) = path.extension().and_then(|ext| ext.to_str()) {
+            if ADR_EXTENSIONS.contains(&ext) {
+                let adr_no = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.strip_prefix(ADR_NAME_PREFIX))
+                    .map(|stem| usize::from_str(stem).expect("ADR number to be a small integer"));
+
+                if let Some(id) = adr_no {
+                    results.push((id, path));
+                }
+            }
+        }
+    })
+    .expect("Visit dir not to fail");
+
+    results.sort_by_key(|(id, _)| *id);
+    results
+}
+
+pub fn collect_adrs() -> Vec<Adr> {
+    discover_adr_paths()
+        .into_iter()
+        .map(|(id, path)| {
+            let events =
+                parse_events(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+            Adr { id, events }
+        })
+        .collect()
+}
+
+/// Same as `collect_adrs`, but a file that currently fails to parse is
+/// logged and skipped rather than panicking the whole process. Used by
+/// long-running callers (e.g. `adr lsp`) for which one malformed ADR
+/// shouldn't prevent startup or diagnostics for every other file.
+pub fn collect_adrs_best_effort() -> Vec<Adr> {
+    discover_adr_paths()
+        .into_iter()
+        .filter_map(|(id, path)| match parse_events(&path) {
+            Ok(events) => Some(Adr { id, events }),
+            Err(e) => {
+                eprintln!("{}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Whether `path` has one of the extensions ADR files are recognized by.
+pub fn is_adr_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ADR_EXTENSIONS.contains(&ext))
+}
@@ -0,0 +1,225 @@
+//! Minimal LSP server for ADR files, modelled on the stdio JSON-RPC message
+//! loop used by rust-analyzer's `gen_lsp_server`.
+//!
+//! Speaks just enough of the protocol to be useful while editing an ADR
+//! file: `textDocument/didOpen` and `textDocument/didChange` re-parse the
+//! in-memory buffer (never the file on disk) and publish diagnostics for
+//! every line that fails to parse, and `textDocument/completion` offers the
+//! stack/category/tech names and action keywords already seen across the
+//! collected ADRs.
+
+use crate::adr;
+use crate::event;
+use crate::radar;
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::io::{self, BufRead, Read, Write};
+
+/// Names already used across the collected ADRs, offered as completion
+/// candidates so authors stay consistent with existing radar entries.
+/// Techs whose latest transition celebrated them off the radar are not
+/// offered.
+struct Workspace {
+    stacks: BTreeSet<String>,
+    categories: BTreeSet<String>,
+    techs: BTreeSet<String>,
+}
+
+impl Workspace {
+    fn load() -> Self {
+        let stacks_table = radar::build_stacks(adr::collect_adrs_best_effort());
+        let mut stacks = BTreeSet::new();
+        let mut categories = BTreeSet::new();
+        let mut techs = BTreeSet::new();
+        for (stack_name, stack) in stacks_table.0 {
+            stacks.insert(stack_name);
+            for (category_name, category) in stack.0 {
+                categories.insert(category_name);
+                techs.extend(
+                    category
+                        .techs
+                        .into_iter()
+                        .filter(|(_, history)| history.current_ring().is_some())
+                        .map(|(name, _)| name),
+                );
+            }
+        }
+        Workspace {
+            stacks,
+            categories,
+            techs,
+        }
+    }
+
+    fn completion_items(&self) -> Vec<Value> {
+        const ACTION_KEYWORDS: [&str; 4] = ["default", "trial", "retire", "celebrate"];
+        self.stacks
+            .iter()
+            .map(|s| completion_item(s, "Stack"))
+            .chain(
+                self.categories
+                    .iter()
+                    .map(|c| completion_item(c, "Category")),
+            )
+            .chain(self.techs.iter().map(|t| completion_item(t, "Tech")))
+            .chain(
+                ACTION_KEYWORDS
+                    .iter()
+                    .map(|a| completion_item(a, "Action")),
+            )
+            .collect()
+    }
+}
+
+fn completion_item(label: &str, detail: &str) -> Value {
+    json!({ "label": label, "detail": detail })
+}
+
+/// Diagnostics for every malformed or unknown-action line in `text`,
+/// expressed as LSP `Diagnostic`s with 0-indexed line/character ranges.
+fn diagnostics_for(text: &str) -> Vec<Value> {
+    let mut diagnostics = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Err(err) = event::parse_line(line, idx + 1) {
+            let line_idx = err.line() - 1;
+            diagnostics.push(json!({
+                "range": {
+                    "start": { "line": line_idx, "character": 0 },
+                    "end": { "line": line_idx, "character": line.chars().count() },
+                },
+                "severity": 1,
+                "source": "adr",
+                "message": err.to_string(),
+            }));
+        }
+    }
+    diagnostics
+}
+
+fn read_message<R: Read + BufRead>(reader: &mut R) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length: ") {
+            content_length = Some(value.parse::<usize>().map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, e)
+            })?);
+        }
+    }
+    let content_length =
+        content_length.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length"))?;
+    let mut buf = vec![0u8; content_length];
+    reader.read_exact(&mut buf)?;
+    Ok(Some(serde_json::from_slice(&buf)?))
+}
+
+fn write_message<W: Write>(writer: &mut W, message: &Value) -> io::Result<()> {
+    let body = serde_json::to_vec(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n", body.len())?;
+    writer.write_all(&body)?;
+    writer.flush()
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn response(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn publish_diagnostics<W: Write>(writer: &mut W, uri: &str, text: &str) -> io::Result<()> {
+    write_message(
+        writer,
+        &notification(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": diagnostics_for(text) }),
+        ),
+    )
+}
+
+/// Runs the `adr lsp` stdio JSON-RPC message loop until stdin closes or a
+/// `shutdown`/`exit` sequence is received.
+pub fn run() {
+    let stdin = io::stdin();
+    let mut reader = stdin.lock();
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+
+    let workspace = Workspace::load();
+    let mut documents: HashMap<String, String> = HashMap::new();
+
+    loop {
+        let message = match read_message(&mut reader) {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(_) => break,
+        };
+
+        let method = message.get("method").and_then(Value::as_str);
+        match method {
+            Some("initialize") => {
+                if let Some(id) = message.get("id").cloned() {
+                    let _ = write_message(
+                        &mut writer,
+                        &response(
+                            id,
+                            json!({
+                                "capabilities": {
+                                    "textDocumentSync": 1,
+                                    "completionProvider": { "resolveProvider": false },
+                                }
+                            }),
+                        ),
+                    );
+                }
+            }
+            Some("textDocument/didOpen") => {
+                if let Some(doc) = message.pointer("/params/textDocument") {
+                    let uri = doc["uri"].as_str().unwrap_or_default().to_string();
+                    let text = doc["text"].as_str().unwrap_or_default().to_string();
+                    let _ = publish_diagnostics(&mut writer, &uri, &text);
+                    documents.insert(uri, text);
+                }
+            }
+            Some("textDocument/didChange") => {
+                if let Some(uri) = message.pointer("/params/textDocument/uri").and_then(Value::as_str) {
+                    let uri = uri.to_string();
+                    if let Some(change) = message
+                        .pointer("/params/contentChanges/0/text")
+                        .and_then(Value::as_str)
+                    {
+                        let text = change.to_string();
+                        let _ = publish_diagnostics(&mut writer, &uri, &text);
+                        documents.insert(uri, text);
+                    }
+                }
+            }
+            Some("textDocument/completion") => {
+                if let Some(id) = message.get("id").cloned() {
+                    let _ = write_message(
+                        &mut writer,
+                        &response(id, json!(workspace.completion_items())),
+                    );
+                }
+            }
+            Some("shutdown") => {
+                if let Some(id) = message.get("id").cloned() {
+                    let _ = write_message(&mut writer, &response(id, Value::Null));
+                }
+            }
+            Some("exit") => break,
+            _ => {}
+        }
+    }
+}
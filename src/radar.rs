@@ -0,0 +1,225 @@
+//! The radar itself: reducing an ADR id-ordered sequence of events into,
+//! for every (stack, category, tech), the full ordered history of ring
+//! transitions — not just the final resting ring — and rendering that as a
+//! markdown table.
+
+use crate::adr::Adr;
+use crate::event::Action;
+use comfy_table::presets::ASCII_MARKDOWN;
+use comfy_table::Table;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+/// One of the three rings a tech can sit in. `None` (not a variant here,
+/// but the `Option<Ring>` callers use) means the tech has been celebrated
+/// out of the radar entirely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ring {
+    Default,
+    Trial,
+    Retire,
+}
+
+impl Ring {
+    fn from_action(action: Action) -> Option<Ring> {
+        match action {
+            Action::Default => Some(Ring::Default),
+            Action::Trial => Some(Ring::Trial),
+            Action::Retire => Some(Ring::Retire),
+            Action::CelebrateRetirement => None,
+        }
+    }
+}
+
+impl Display for Ring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Ring::Default => "Default",
+            Ring::Trial => "Trial",
+            Ring::Retire => "Retire",
+        })
+    }
+}
+
+/// A tech's trajectory through the radar, classified by comparing its ring
+/// in the latest ADR against its ring in the previous one that touched it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Movement {
+    /// First time this tech has appeared anywhere on the radar.
+    New,
+    /// Re-entered a ring after having been celebrated out of it.
+    MovedIn,
+    /// Changed ring (e.g. trial -> default).
+    Changed,
+    /// Celebrated out of the retire ring; no longer on the radar.
+    MovedOut,
+    /// Same ring as the previous ADR that mentioned it.
+    NoChange,
+}
+
+impl Display for Movement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Movement::New => "new",
+            Movement::MovedIn => "moved in",
+            Movement::Changed => "changed",
+            Movement::MovedOut => "moved out",
+            Movement::NoChange => "no change",
+        })
+    }
+}
+
+/// The ordered history of `(adr_id, action)` transitions for one tech
+/// within one (stack, category).
+#[derive(Debug, Clone, Default)]
+pub struct TechHistory {
+    pub transitions: Vec<(usize, Action)>,
+}
+
+impl TechHistory {
+    fn push(&mut self, adr_id: usize, action: Action) {
+        self.transitions.push((adr_id, action));
+    }
+
+    /// The ring this tech currently sits in, or `None` if its most recent
+    /// transition celebrated it out of the radar.
+    pub fn current_ring(&self) -> Option<Ring> {
+        self.transitions
+            .last()
+            .and_then(|(_, action)| Ring::from_action(*action))
+    }
+
+    /// Classifies the latest transition by comparing the ring it produced
+    /// against the ring produced by the transition before it (if any).
+    pub fn movement(&self) -> Movement {
+        let previous_ring = self
+            .transitions
+            .len()
+            .checked_sub(2)
+            .and_then(|i| self.transitions.get(i))
+            .and_then(|(_, action)| Ring::from_action(*action));
+        match (previous_ring, self.current_ring()) {
+            (None, Some(_)) if self.transitions.len() == 1 => Movement::New,
+            (None, Some(_)) => Movement::MovedIn,
+            (Some(_), None) => Movement::MovedOut,
+            (Some(prev), Some(curr)) if prev != curr => Movement::Changed,
+            _ => Movement::NoChange,
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct TechCategory {
+    pub techs: BTreeMap<String, TechHistory>,
+}
+
+#[derive(Default)]
+pub struct Stack(pub BTreeMap<String, TechCategory>);
+
+#[derive(Default)]
+pub struct Stacks(pub BTreeMap<String, Stack>);
+
+/// Reduces an ADR id-ordered sequence of events into the full transition
+/// history per (stack, category, tech). `adrs` must already be sorted by
+/// id: each event appends to that tech's history, so `current_ring` and
+/// `movement` reflect deterministic replay in ADR id order even when only a
+/// middle ADR's events changed since the last recompute.
+pub fn build_stacks(adrs: impl IntoIterator<Item = Adr>) -> Stacks {
+    let mut stacks: Stacks = Default::default();
+    for adr in adrs {
+        for event in adr.events {
+            let stack = stacks.0.entry(event.stack).or_default();
+            let category = stack.0.entry(event.category).or_default();
+            category
+                .techs
+                .entry(event.tech)
+                .or_default()
+                .push(adr.id, event.action);
+        }
+    }
+    stacks
+}
+
+impl Display for Stack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut table = Table::new();
+        table
+            .load_preset(ASCII_MARKDOWN)
+            .set_header(vec!["Category", "Tech", "Ring", "Movement"]);
+        for (category_name, category) in self.0.iter() {
+            for (tech_name, history) in category.techs.iter() {
+                let ring = history
+                    .current_ring()
+                    .map_or_else(|| "Retired".to_string(), |ring| ring.to_string());
+                table.add_row(vec![
+                    category_name.clone(),
+                    tech_name.clone(),
+                    ring,
+                    history.movement().to_string(),
+                ]);
+            }
+        }
+
+        f.write_fmt(format_args!("{}", table))
+    }
+}
+
+impl Display for Stacks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, stack) in self.0.iter() {
+            f.write_fmt(format_args!("## {}\n\n", name))?;
+            f.write_fmt(format_args!("{}\n\n", stack))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(transitions: &[(usize, Action)]) -> TechHistory {
+        TechHistory {
+            transitions: transitions.to_vec(),
+        }
+    }
+
+    #[test]
+    fn first_appearance_is_new() {
+        let h = history(&[(1, Action::Trial)]);
+        assert_eq!(h.movement(), Movement::New);
+        assert_eq!(h.current_ring(), Some(Ring::Trial));
+    }
+
+    #[test]
+    fn ring_change_is_changed() {
+        let h = history(&[(1, Action::Trial), (2, Action::Default)]);
+        assert_eq!(h.movement(), Movement::Changed);
+        assert_eq!(h.current_ring(), Some(Ring::Default));
+    }
+
+    #[test]
+    fn same_ring_again_is_no_change() {
+        let h = history(&[(1, Action::Trial), (2, Action::Trial)]);
+        assert_eq!(h.movement(), Movement::NoChange);
+    }
+
+    #[test]
+    fn celebrate_and_stay_out_is_moved_out_with_no_current_ring() {
+        let h = history(&[(1, Action::Retire), (2, Action::CelebrateRetirement)]);
+        assert_eq!(h.movement(), Movement::MovedOut);
+        assert_eq!(h.current_ring(), None);
+    }
+
+    #[test]
+    fn celebrate_then_reenter_is_moved_in() {
+        let h = history(&[
+            (1, Action::Retire),
+            (2, Action::CelebrateRetirement),
+            (3, Action::Trial),
+        ]);
+        assert_eq!(h.movement(), Movement::MovedIn);
+        assert_eq!(h.current_ring(), Some(Ring::Trial));
+    }
+}
@@ -0,0 +1,152 @@
+//! Incremental `adr watch` mode.
+//!
+//! A small salsa-style memoized query layer sits in front of the radar
+//! reduction: each ADR file's parsed `Events` are cached keyed on path and
+//! content hash, so a filesystem change only re-parses the one file that
+//! changed. The `Stacks` reduction itself is order-sensitive (each event
+//! appends to its tech's transition history in ADR id order), so it is
+//! always replayed across the full sorted ADR id sequence — every file is
+//! still read and hashed, but most of that sequence's `Events` are served
+//! from cache instead of re-parsed.
+
+use crate::adr::{self, Adr};
+use crate::event::{self, Events};
+use crate::radar;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+/// How long to wait for more filesystem events after the first one before
+/// recomputing, so a burst of saves collapses into a single recompute.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+type ContentHash = u64;
+
+fn hash_content(content: &str) -> ContentHash {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Memoized per-file parse: a cache hit means the file's on-disk bytes are
+/// unchanged since the last recompute, so the previously parsed `Events`
+/// are reused without re-running the lexer/parser.
+#[derive(Default)]
+struct EventCache {
+    entries: HashMap<PathBuf, (ContentHash, Events)>,
+}
+
+impl EventCache {
+    fn events_for(&mut self, path: &PathBuf) -> Option<Events> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("{}: {}", path.display(), e);
+                return None;
+            }
+        };
+        let hash = hash_content(&content);
+        if let Some((cached_hash, cached_events)) = self.entries.get(path) {
+            if *cached_hash == hash {
+                return Some(cached_events.clone());
+            }
+        }
+        let events = match event::parse(&content) {
+            Ok(events) => events,
+            Err(e) => {
+                eprintln!("{}: {}", path.display(), e);
+                return None;
+            }
+        };
+        self.entries.insert(path.clone(), (hash, events.clone()));
+        Some(events)
+    }
+
+    /// Forces the next `events_for` call for `path` to re-read and
+    /// re-parse, regardless of whether the content hash actually changed.
+    fn invalidate(&mut self, path: &PathBuf) {
+        self.entries.remove(path);
+    }
+}
+
+fn recompute_and_emit(cache: &mut EventCache, output: Option<&str>) {
+    let adrs: Vec<Adr> = adr::discover_adr_paths()
+        .into_iter()
+        .filter_map(|(id, path)| cache.events_for(&path).map(|events| Adr { id, events }))
+        .collect();
+
+    // `discover_adr_paths` is sorted by id, so replaying the reduction in
+    // this order reproduces exactly the result a full batch run would, even
+    // though only the changed file(s) were actually re-parsed above.
+    let stacks = radar::build_stacks(adrs);
+    let rendered = stacks.to_string();
+
+    println!("{}", rendered);
+    if let Some(path) = output {
+        if let Err(e) = std::fs::write(path, &rendered) {
+            eprintln!("failed to write {}: {}", path, e);
+        }
+    }
+}
+
+/// Runs `adr watch`: recomputes the radar once up front, then again after
+/// every debounced batch of filesystem changes, until the watcher channel
+/// disconnects.
+pub fn run(output: Option<String>) {
+    let (tx, rx) = channel();
+    let mut watcher = RecommendedWatcher::new(
+        move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let _ = tx.send(event);
+            }
+        },
+        notify::Config::default(),
+    )
+    .expect("failed to start filesystem watcher");
+    watcher
+        .watch(std::path::Path::new("./"), RecursiveMode::Recursive)
+        .expect("failed to watch current directory");
+
+    let mut cache = EventCache::default();
+    recompute_and_emit(&mut cache, output.as_deref());
+
+    // `notify` watches `./` recursively, so this also sees routine build
+    // and VCS churn (`target/`, `.git/`, ...). Only ADR files should
+    // extend the debounce window or trigger a recompute.
+    let adr_paths =
+        |event: notify::Event| event.paths.into_iter().filter(|p| adr::is_adr_file(p));
+
+    while let Ok(first) = rx.recv() {
+        let mut changed = false;
+        for path in adr_paths(first) {
+            cache.invalidate(&path);
+            changed = true;
+        }
+        if !changed {
+            continue;
+        }
+
+        let mut deadline = Instant::now() + DEBOUNCE;
+        loop {
+            match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(event) => {
+                    for path in adr_paths(event) {
+                        cache.invalidate(&path);
+                        changed = true;
+                        deadline = Instant::now() + DEBOUNCE;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        if changed {
+            recompute_and_emit(&mut cache, output.as_deref());
+        }
+    }
+}